@@ -8,6 +8,13 @@
 //!   They're similar to JsError, in that they have line numbers. But
 //!   Diagnostics are compile-time type errors, whereas JsErrors are runtime
 //!   exceptions.
+//!
+//! This module also hosts [`JsErrorClass`] and the embedder-extensible
+//! registry that [`get_error_class_name`] consults for classifiers that
+//! can't be wired in here directly (e.g. out-of-tree ops). `JsErrorClass`
+//! lives here rather than in `deno_core` for now, which means it only
+//! centralizes classification logic next to its match arm, not next to
+//! each error enum's own definition — see the doc comment on the trait.
 
 use deno_broadcast_channel::BroadcastChannelError;
 use deno_cache::CacheError;
@@ -38,27 +45,79 @@ use std::env;
 use std::error::Error;
 use std::io;
 use std::sync::Arc;
-
-fn get_dlopen_error_class(error: &dlopen2::Error) -> &'static str {
-  use dlopen2::Error::*;
-  match error {
-    NullCharacter(_) => "InvalidData",
-    OpeningLibraryError(ref e) => get_io_error_class(e),
-    SymbolGettingError(ref e) => get_io_error_class(e),
-    AddrNotMatchingDll(ref e) => get_io_error_class(e),
-    NullSymbol => "NotFound",
+use std::sync::RwLock;
+
+/// Implemented by error types that know their own JS-facing error class.
+///
+/// This crate (`deno_runtime`) depends on every op crate that owns an
+/// error type classified here, so those crates can't depend back on it to
+/// implement this trait themselves without a cycle — that's exactly why
+/// `get_error_class_name` used to need a hand-maintained downcast chain
+/// instead of each crate just implementing a trait. The impls below are
+/// still all in this file, next to the match arm each one replaces, not
+/// next to their error enum's definition.
+///
+/// The actual decentralization this trait is meant to enable needs it (or
+/// a re-export of it) to live in `deno_core`, which every op crate already
+/// depends on — `deno_core` isn't part of this checkout, so that move is
+/// left for a follow-up. Until then, this trait mainly buys a named
+/// `error_class()` method per type instead of an anonymous `get_*` free
+/// function; an embedder whose error type is not listed in
+/// [`get_error_class_name`] uses [`register_error_class_classifier`].
+pub trait JsErrorClass: Error {
+  fn error_class(&self) -> &'static str;
+}
+
+/// A classifier an embedder registers to teach [`get_error_class_name`]
+/// about an error type that its built-in downcast list does not name.
+/// Implementing [`JsErrorClass`] for such a type does not by itself make
+/// `get_error_class_name` consult it.
+pub type ErrorClassifier = fn(&AnyError) -> Option<&'static str>;
+
+static EMBEDDER_CLASSIFIERS: RwLock<Vec<ErrorClassifier>> =
+  RwLock::new(Vec::new());
+
+/// Registers an additional classifier consulted by [`get_error_class_name`]
+/// after the built-in [`JsErrorClass`] fast path. Embedders use this to
+/// plug in error types defined entirely outside this crate without editing
+/// this file. Nothing in this crate registers a classifier through this
+/// function yet — `deno_webgpu` and `deno_websocket` below are still
+/// hardcoded `.or_else()` calls, not registry consumers — but out-of-tree
+/// extensions can use it today.
+pub fn register_error_class_classifier(classifier: ErrorClassifier) {
+  EMBEDDER_CLASSIFIERS.write().unwrap().push(classifier);
+}
+
+fn downcast_class<T: JsErrorClass + Send + Sync + 'static>(
+  e: &AnyError,
+) -> Option<&'static str> {
+  e.downcast_ref::<T>().map(JsErrorClass::error_class)
+}
+
+impl JsErrorClass for dlopen2::Error {
+  fn error_class(&self) -> &'static str {
+    use dlopen2::Error::*;
+    match self {
+      NullCharacter(_) => "InvalidData",
+      OpeningLibraryError(ref e) => get_io_error_class(e),
+      SymbolGettingError(ref e) => get_io_error_class(e),
+      AddrNotMatchingDll(ref e) => get_io_error_class(e),
+      NullSymbol => "NotFound",
+    }
   }
 }
 
-fn get_env_var_error_class(error: &env::VarError) -> &'static str {
-  use env::VarError::*;
-  match error {
-    NotPresent => "NotFound",
-    NotUnicode(..) => "InvalidData",
+impl JsErrorClass for env::VarError {
+  fn error_class(&self) -> &'static str {
+    use env::VarError::*;
+    match self {
+      NotPresent => "NotFound",
+      NotUnicode(..) => "InvalidData",
+    }
   }
 }
 
-fn get_io_error_class(error: &io::Error) -> &'static str {
+pub fn get_io_error_class(error: &io::Error) -> &'static str {
   use io::ErrorKind::*;
   match error.kind() {
     NotFound => "NotFound",
@@ -94,348 +153,489 @@ fn get_io_error_class(error: &io::Error) -> &'static str {
   }
 }
 
-fn get_module_resolution_error_class(
-  _: &ModuleResolutionError,
-) -> &'static str {
-  "URIError"
+impl JsErrorClass for io::Error {
+  fn error_class(&self) -> &'static str {
+    get_io_error_class(self)
+  }
 }
 
-fn get_notify_error_class(error: &notify::Error) -> &'static str {
-  use notify::ErrorKind::*;
-  match error.kind {
-    Generic(_) => "Error",
-    Io(ref e) => get_io_error_class(e),
-    PathNotFound => "NotFound",
-    WatchNotFound => "NotFound",
-    InvalidConfig(_) => "InvalidData",
-    MaxFilesWatch => "Error",
+impl JsErrorClass for ModuleResolutionError {
+  fn error_class(&self) -> &'static str {
+    "URIError"
   }
 }
 
-fn get_regex_error_class(error: &regex::Error) -> &'static str {
-  use regex::Error::*;
-  match error {
-    Syntax(_) => "SyntaxError",
-    CompiledTooBig(_) => "RangeError",
-    _ => "Error",
+impl JsErrorClass for notify::Error {
+  fn error_class(&self) -> &'static str {
+    use notify::ErrorKind::*;
+    match &self.kind {
+      Generic(_) => "Error",
+      Io(ref e) => get_io_error_class(e),
+      PathNotFound => "NotFound",
+      WatchNotFound => "NotFound",
+      InvalidConfig(_) => "InvalidData",
+      MaxFilesWatch => "Error",
+    }
   }
 }
 
-fn get_serde_json_error_class(
-  error: &serde_json::error::Error,
-) -> &'static str {
-  use deno_core::serde_json::error::*;
-  match error.classify() {
-    Category::Io => error
-      .source()
-      .and_then(|e| e.downcast_ref::<io::Error>())
-      .map(get_io_error_class)
-      .unwrap(),
-    Category::Syntax => "SyntaxError",
-    Category::Data => "InvalidData",
-    Category::Eof => "UnexpectedEof",
+impl JsErrorClass for regex::Error {
+  fn error_class(&self) -> &'static str {
+    use regex::Error::*;
+    match self {
+      Syntax(_) => "SyntaxError",
+      CompiledTooBig(_) => "RangeError",
+      _ => "Error",
+    }
   }
 }
 
-fn get_url_parse_error_class(_error: &url::ParseError) -> &'static str {
-  "URIError"
+impl JsErrorClass for serde_json::error::Error {
+  fn error_class(&self) -> &'static str {
+    use deno_core::serde_json::error::*;
+    match self.classify() {
+      Category::Io => self
+        .source()
+        .and_then(|e| e.downcast_ref::<io::Error>())
+        .map(get_io_error_class)
+        .unwrap(),
+      Category::Syntax => "SyntaxError",
+      Category::Data => "InvalidData",
+      Category::Eof => "UnexpectedEof",
+    }
+  }
 }
 
-fn get_hyper_error_class(_error: &hyper::Error) -> &'static str {
-  "Http"
+impl JsErrorClass for url::ParseError {
+  fn error_class(&self) -> &'static str {
+    "URIError"
+  }
 }
 
-fn get_hyper_util_error_class(
-  _error: &hyper_util::client::legacy::Error,
-) -> &'static str {
-  "Http"
+impl JsErrorClass for hyper::Error {
+  fn error_class(&self) -> &'static str {
+    "Http"
+  }
+}
+
+impl JsErrorClass for hyper_util::client::legacy::Error {
+  fn error_class(&self) -> &'static str {
+    "Http"
+  }
 }
 
-fn get_hyper_v014_error_class(_error: &hyper_v014::Error) -> &'static str {
-  "Http"
+impl JsErrorClass for hyper_v014::Error {
+  fn error_class(&self) -> &'static str {
+    "Http"
+  }
 }
 
 #[cfg(unix)]
-pub fn get_nix_error_class(error: &nix::Error) -> &'static str {
-  match error {
-    nix::Error::ECHILD => "NotFound",
-    nix::Error::EINVAL => "TypeError",
-    nix::Error::ENOENT => "NotFound",
-    nix::Error::ENOTTY => "BadResource",
-    nix::Error::EPERM => "PermissionDenied",
-    nix::Error::ESRCH => "NotFound",
-    nix::Error::ELOOP => "FilesystemLoop",
-    nix::Error::ENOTDIR => "NotADirectory",
-    nix::Error::ENETUNREACH => "NetworkUnreachable",
-    nix::Error::EISDIR => "IsADirectory",
-    nix::Error::UnknownErrno => "Error",
-    &nix::Error::ENOTSUP => unreachable!(),
-    _ => "Error",
+impl JsErrorClass for nix::Error {
+  fn error_class(&self) -> &'static str {
+    match self {
+      nix::Error::ECHILD => "NotFound",
+      nix::Error::EINVAL => "TypeError",
+      nix::Error::ENOENT => "NotFound",
+      nix::Error::ENOTTY => "BadResource",
+      nix::Error::EPERM => "PermissionDenied",
+      nix::Error::ESRCH => "NotFound",
+      nix::Error::ELOOP => "FilesystemLoop",
+      nix::Error::ENOTDIR => "NotADirectory",
+      nix::Error::ENETUNREACH => "NetworkUnreachable",
+      nix::Error::EISDIR => "IsADirectory",
+      nix::Error::UnknownErrno => "Error",
+      &nix::Error::ENOTSUP => unreachable!(),
+      _ => "Error",
+    }
   }
 }
 
-fn get_web_error_class(e: &WebError) -> &'static str {
-  match e {
-    WebError::Base64Decode => "DOMExceptionInvalidCharacterError",
-    WebError::InvalidEncodingLabel(_) => "RangeError",
-    WebError::BufferTooLong => "TypeError",
-    WebError::ValueTooLarge => "RangeError",
-    WebError::BufferTooSmall => "RangeError",
-    WebError::DataInvalid => "TypeError",
-    WebError::DataError(_) => "Error",
+impl JsErrorClass for WebError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      WebError::Base64Decode => "DOMExceptionInvalidCharacterError",
+      WebError::InvalidEncodingLabel(_) => "RangeError",
+      WebError::BufferTooLong => "TypeError",
+      WebError::ValueTooLarge => "RangeError",
+      WebError::BufferTooSmall => "RangeError",
+      WebError::DataInvalid => "TypeError",
+      WebError::DataError(_) => "Error",
+    }
   }
 }
 
-fn get_web_compression_error_class(e: &CompressionError) -> &'static str {
-  match e {
-    CompressionError::UnsupportedFormat => "TypeError",
-    CompressionError::ResourceClosed => "TypeError",
-    CompressionError::IoTypeError(_) => "TypeError",
-    CompressionError::Io(e) => get_io_error_class(e),
+impl JsErrorClass for CompressionError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      CompressionError::UnsupportedFormat => "TypeError",
+      CompressionError::ResourceClosed => "TypeError",
+      CompressionError::IoTypeError(_) => "TypeError",
+      CompressionError::Io(e) => get_io_error_class(e),
+    }
   }
 }
 
-fn get_web_message_port_error_class(e: &MessagePortError) -> &'static str {
-  match e {
-    MessagePortError::InvalidTransfer => "TypeError",
-    MessagePortError::NotReady => "TypeError",
-    MessagePortError::TransferSelf => "TypeError",
-    MessagePortError::Canceled(e) => {
-      let io_err: io::Error = e.to_owned().into();
-      get_io_error_class(&io_err)
+impl JsErrorClass for MessagePortError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      MessagePortError::InvalidTransfer => "TypeError",
+      MessagePortError::NotReady => "TypeError",
+      MessagePortError::TransferSelf => "TypeError",
+      MessagePortError::Canceled(e) => {
+        let io_err: io::Error = e.to_owned().into();
+        get_io_error_class(&io_err)
+      }
+      MessagePortError::Resource(e) => {
+        get_error_class_name(e).unwrap_or("Error")
+      }
     }
-    MessagePortError::Resource(e) => get_error_class_name(e).unwrap_or("Error"),
   }
 }
 
-fn get_web_stream_resource_error_class(
-  e: &StreamResourceError,
-) -> &'static str {
-  match e {
-    StreamResourceError::Canceled(e) => {
-      let io_err: io::Error = e.to_owned().into();
-      get_io_error_class(&io_err)
+impl JsErrorClass for StreamResourceError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      StreamResourceError::Canceled(e) => {
+        let io_err: io::Error = e.to_owned().into();
+        get_io_error_class(&io_err)
+      }
+      StreamResourceError::Js(_) => "TypeError",
     }
-    StreamResourceError::Js(_) => "TypeError",
   }
 }
 
-fn get_web_blob_error_class(e: &BlobError) -> &'static str {
-  match e {
-    BlobError::BlobPartNotFound => "TypeError",
-    BlobError::SizeLargerThanBlobPart => "TypeError",
-    BlobError::BlobURLsNotSupported => "TypeError",
-    BlobError::Url(_) => "Error",
+impl JsErrorClass for BlobError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      BlobError::BlobPartNotFound => "TypeError",
+      BlobError::SizeLargerThanBlobPart => "TypeError",
+      BlobError::BlobURLsNotSupported => "TypeError",
+      BlobError::Url(_) => "Error",
+    }
   }
 }
 
-fn get_ffi_repr_error_class(e: &ReprError) -> &'static str {
-  match e {
-    ReprError::InvalidOffset => "TypeError",
-    ReprError::InvalidArrayBuffer => "TypeError",
-    ReprError::DestinationLengthTooShort => "RangeError",
-    ReprError::InvalidCString => "TypeError",
-    ReprError::CStringTooLong => "TypeError",
-    ReprError::InvalidBool => "TypeError",
-    ReprError::InvalidU8 => "TypeError",
-    ReprError::InvalidI8 => "TypeError",
-    ReprError::InvalidU16 => "TypeError",
-    ReprError::InvalidI16 => "TypeError",
-    ReprError::InvalidU32 => "TypeError",
-    ReprError::InvalidI32 => "TypeError",
-    ReprError::InvalidU64 => "TypeError",
-    ReprError::InvalidI64 => "TypeError",
-    ReprError::InvalidF32 => "TypeError",
-    ReprError::InvalidF64 => "TypeError",
-    ReprError::InvalidPointer => "TypeError",
-    ReprError::Permission(e) => get_error_class_name(e).unwrap_or("Error"),
+impl JsErrorClass for ReprError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      ReprError::InvalidOffset => "TypeError",
+      ReprError::InvalidArrayBuffer => "TypeError",
+      ReprError::DestinationLengthTooShort => "RangeError",
+      ReprError::InvalidCString => "TypeError",
+      ReprError::CStringTooLong => "TypeError",
+      ReprError::InvalidBool => "TypeError",
+      ReprError::InvalidU8 => "TypeError",
+      ReprError::InvalidI8 => "TypeError",
+      ReprError::InvalidU16 => "TypeError",
+      ReprError::InvalidI16 => "TypeError",
+      ReprError::InvalidU32 => "TypeError",
+      ReprError::InvalidI32 => "TypeError",
+      ReprError::InvalidU64 => "TypeError",
+      ReprError::InvalidI64 => "TypeError",
+      ReprError::InvalidF32 => "TypeError",
+      ReprError::InvalidF64 => "TypeError",
+      ReprError::InvalidPointer => "TypeError",
+      ReprError::Permission(e) => get_error_class_name(e).unwrap_or("Error"),
+    }
   }
 }
 
-fn get_ffi_dlfcn_error_class(e: &DlfcnError) -> &'static str {
-  match e {
-    DlfcnError::RegisterSymbol { .. } => "Error",
-    DlfcnError::Dlopen(_) => "Error",
-    DlfcnError::Permission(e) => get_error_class_name(e).unwrap_or("Error"),
-    DlfcnError::Other(e) => get_error_class_name(e).unwrap_or("Error"),
+impl JsErrorClass for DlfcnError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      DlfcnError::RegisterSymbol { .. } => "Error",
+      DlfcnError::Dlopen(_) => "Error",
+      DlfcnError::Permission(e) => get_error_class_name(e).unwrap_or("Error"),
+      DlfcnError::Other(e) => get_error_class_name(e).unwrap_or("Error"),
+    }
   }
 }
 
-fn get_ffi_static_error_class(e: &StaticError) -> &'static str {
-  match e {
-    StaticError::Dlfcn(e) => get_ffi_dlfcn_error_class(e),
-    StaticError::InvalidTypeVoid => "TypeError",
-    StaticError::InvalidTypeStruct => "TypeError",
-    StaticError::Resource(e) => get_error_class_name(e).unwrap_or("Error"),
+impl JsErrorClass for StaticError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      StaticError::Dlfcn(e) => e.error_class(),
+      StaticError::InvalidTypeVoid => "TypeError",
+      StaticError::InvalidTypeStruct => "TypeError",
+      StaticError::Resource(e) => get_error_class_name(e).unwrap_or("Error"),
+    }
   }
 }
 
-fn get_ffi_callback_error_class(e: &CallbackError) -> &'static str {
-  match e {
-    CallbackError::Resource(e) => get_error_class_name(e).unwrap_or("Error"),
-    CallbackError::Other(e) => get_error_class_name(e).unwrap_or("Error"),
-    CallbackError::Permission(e) => get_error_class_name(e).unwrap_or("Error"),
+impl JsErrorClass for CallbackError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      CallbackError::Resource(e) => get_error_class_name(e).unwrap_or("Error"),
+      CallbackError::Other(e) => get_error_class_name(e).unwrap_or("Error"),
+      CallbackError::Permission(e) => {
+        get_error_class_name(e).unwrap_or("Error")
+      }
+    }
   }
 }
 
-fn get_ffi_call_error_class(e: &CallError) -> &'static str {
-  match e {
-    CallError::IR(_) => "TypeError",
-    CallError::NonblockingCallFailure(_) => "Error",
-    CallError::InvalidSymbol(_) => "TypeError",
-    CallError::Permission(e) => get_error_class_name(e).unwrap_or("Error"),
-    CallError::Callback(e) => get_ffi_callback_error_class(e),
+impl JsErrorClass for CallError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      CallError::IR(_) => "TypeError",
+      CallError::NonblockingCallFailure(_) => "Error",
+      CallError::InvalidSymbol(_) => "TypeError",
+      CallError::Permission(e) => get_error_class_name(e).unwrap_or("Error"),
+      CallError::Callback(e) => e.error_class(),
+    }
   }
 }
 
-fn get_webstorage_class_name(e: &WebStorageError) -> &'static str {
-  match e {
-    WebStorageError::ContextNotSupported => "DOMExceptionNotSupportedError",
-    WebStorageError::Sqlite(_) => todo!(),
-    WebStorageError::Io(e) => get_io_error_class(e),
-    WebStorageError::StorageExceeded => "DOMExceptionQuotaExceededError",
+/// Shared by `deno_cache`, `deno_kv`, and `deno_webstorage`, all three of
+/// which persist to a SQLite-backed store and want the same JS-facing
+/// classes for the same underlying result codes.
+fn get_sqlite_error_class(error: &rusqlite::Error) -> &'static str {
+  use rusqlite::ffi::ErrorCode::*;
+  match error {
+    rusqlite::Error::SqliteFailure(e, _) => match e.code {
+      DatabaseBusy | DatabaseLocked => "Busy",
+      DiskFull | TooBig => "DOMExceptionQuotaExceededError",
+      ConstraintViolation => "TypeError",
+      DatabaseCorrupt | NotADatabase => "InvalidData",
+      CannotOpen | ReadOnly => "PermissionDenied",
+      // Gap vs. the original ask: SQLITE_IOERR* was supposed to route
+      // through get_io_error_class, but ErrorCode::SystemIoFailure carries
+      // no errno, so there's no io::ErrorKind to recover it from. Falls
+      // back to "Error" (and so does anything else unmapped) like the
+      // rest of this match.
+      _ => "Error",
+    },
+    _ => "Error",
   }
 }
 
-fn get_tls_error_class(e: &TlsError) -> &'static str {
-  match e {
-    TlsError::Rustls(_) => "Error",
-    TlsError::UnableAddPemFileToCert(e) => get_io_error_class(e),
-    TlsError::CertInvalid
-    | TlsError::CertsNotFound
-    | TlsError::KeysNotFound
-    | TlsError::KeyDecode => "InvalidData",
+impl JsErrorClass for WebStorageError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      WebStorageError::ContextNotSupported => {
+        "DOMExceptionNotSupportedError"
+      }
+      WebStorageError::Sqlite(e) => get_sqlite_error_class(e),
+      WebStorageError::Io(e) => get_io_error_class(e),
+      WebStorageError::StorageExceeded => "DOMExceptionQuotaExceededError",
+    }
   }
 }
 
-pub fn get_cron_error_class(e: &CronError) -> &'static str {
-  match e {
-    CronError::Resource(e) => {
-      deno_core::error::get_custom_error_class(e).unwrap_or("Error")
+/// Destructures a `rustls::Error` into a granular class instead of the
+/// generic `"Error"`, so JS code can tell an expired cert from an unknown
+/// CA from a protocol mismatch.
+fn get_rustls_error_class(error: &rustls::Error) -> &'static str {
+  use rustls::CertificateError::*;
+  use rustls::Error::*;
+  match error {
+    InvalidCertificate(Expired | NotValidYet) => "CertificateExpired",
+    InvalidCertificate(UnknownIssuer | BadSignature | Revoked) => {
+      "CertificateInvalid"
+    }
+    InvalidCertificate(NotValidForName) => "CertificateHostnameMismatch",
+    AlertReceived(desc) => get_rustls_alert_error_class(desc),
+    PeerIncompatible(_) | PeerMisbehaved(_) | NoCertificatesPresented => {
+      "TlsHandshakeFailed"
     }
-    CronError::NameExceeded(_) => "TypeError",
-    CronError::NameInvalid => "TypeError",
-    CronError::AlreadyExists => "TypeError",
-    CronError::TooManyCrons => "TypeError",
-    CronError::InvalidCron => "TypeError",
-    CronError::InvalidBackoff => "TypeError",
-    CronError::AcquireError(_) => "Error",
-    CronError::Other(e) => get_error_class_name(e).unwrap_or("Error"),
+    _ => "Error",
   }
 }
 
-fn get_canvas_error(e: &CanvasError) -> &'static str {
-  match e {
-    CanvasError::UnsupportedColorType(_) => "TypeError",
-    CanvasError::Image(_) => "Error",
+fn get_rustls_alert_error_class(
+  desc: &rustls::AlertDescription,
+) -> &'static str {
+  use rustls::AlertDescription::*;
+  match desc {
+    CertificateExpired => "CertificateExpired",
+    CertificateUnknown | UnknownCA | BadCertificate | CertificateRevoked => {
+      "CertificateInvalid"
+    }
+    _ => "TlsHandshakeFailed",
   }
 }
 
-pub fn get_cache_error(error: &CacheError) -> &'static str {
-  match error {
-    CacheError::Sqlite(_) => "Error",
-    CacheError::JoinError(_) => "Error",
-    CacheError::Resource(err) => {
-      deno_core::error::get_custom_error_class(err).unwrap_or("Error")
+// metatypedev/deno#chunk0-5 (build-time selectable native-tls backend with
+// matching error classification) is BLOCKED / out of scope for this
+// checkout: it needs a native-tls backend plus a default-tls/native-tls/
+// native-tls-alpn/native-tls-vendored Cargo feature split in deno_tls and
+// deno_net, and a new TlsError::NativeTls variant, none of which this tree
+// has a Cargo.toml or crate source to add. No native-tls classification
+// exists below.
+impl JsErrorClass for TlsError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      TlsError::Rustls(e) => get_rustls_error_class(e),
+      TlsError::UnableAddPemFileToCert(e) => get_io_error_class(e),
+      TlsError::CertInvalid
+      | TlsError::CertsNotFound
+      | TlsError::KeysNotFound
+      | TlsError::KeyDecode => "InvalidData",
     }
-    CacheError::Other(e) => get_error_class_name(e).unwrap_or("Error"),
-    CacheError::Io(err) => get_io_error_class(err),
   }
 }
 
-fn get_broadcast_channel_error(error: &BroadcastChannelError) -> &'static str {
-  match error {
-    BroadcastChannelError::Resource(err) => {
-      deno_core::error::get_custom_error_class(err).unwrap()
+impl JsErrorClass for CronError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      CronError::Resource(e) => {
+        deno_core::error::get_custom_error_class(e).unwrap_or("Error")
+      }
+      CronError::NameExceeded(_) => "TypeError",
+      CronError::NameInvalid => "TypeError",
+      CronError::AlreadyExists => "TypeError",
+      CronError::TooManyCrons => "TypeError",
+      CronError::InvalidCron => "TypeError",
+      CronError::InvalidBackoff => "TypeError",
+      CronError::AcquireError(_) => "Error",
+      CronError::Other(e) => get_error_class_name(e).unwrap_or("Error"),
     }
-    BroadcastChannelError::MPSCSendError(_) => "Error",
-    BroadcastChannelError::BroadcastSendError(_) => "Error",
-    BroadcastChannelError::Other(err) => {
-      get_error_class_name(err).unwrap_or("Error")
+  }
+}
+
+impl JsErrorClass for CanvasError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      CanvasError::UnsupportedColorType(_) => "TypeError",
+      CanvasError::Image(_) => "Error",
     }
   }
 }
 
-fn get_kv_error(error: &KvError) -> &'static str {
-  match error {
-    KvError::DatabaseHandler(e) | KvError::Resource(e) | KvError::Kv(e) => {
-      get_error_class_name(e).unwrap_or("Error")
+impl JsErrorClass for CacheError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      CacheError::Sqlite(e) => get_sqlite_error_class(e),
+      CacheError::JoinError(_) => "Error",
+      CacheError::Resource(err) => {
+        deno_core::error::get_custom_error_class(err).unwrap_or("Error")
+      }
+      CacheError::Other(e) => get_error_class_name(e).unwrap_or("Error"),
+      CacheError::Io(err) => get_io_error_class(err),
     }
-    KvError::TooManyRanges(_) => "TypeError",
-    KvError::TooManyEntries(_) => "TypeError",
-    KvError::TooManyChecks(_) => "TypeError",
-    KvError::TooManyMutations(_) => "TypeError",
-    KvError::TooManyKeys(_) => "TypeError",
-    KvError::InvalidLimit => "TypeError",
-    KvError::InvalidBoundaryKey => "TypeError",
-    KvError::KeyTooLargeToRead(_) => "TypeError",
-    KvError::KeyTooLargeToWrite(_) => "TypeError",
-    KvError::TotalMutationTooLarge(_) => "TypeError",
-    KvError::TotalKeyTooLarge(_) => "TypeError",
-    KvError::Io(e) => get_io_error_class(e),
-    KvError::QueueMessageNotFound => "TypeError",
-    KvError::StartKeyNotInKeyspace => "TypeError",
-    KvError::EndKeyNotInKeyspace => "TypeError",
-    KvError::StartKeyGreaterThanEndKey => "TypeError",
-    KvError::InvalidCheck(e) => match e {
-      KvCheckError::InvalidVersionstamp => "TypeError",
-      KvCheckError::Io(e) => get_io_error_class(e),
-    },
-    KvError::InvalidMutation(e) => match e {
-      KvMutationError::BigInt(_) => "Error",
-      KvMutationError::Io(e) => get_io_error_class(e),
-      KvMutationError::InvalidMutationWithValue(_) => "TypeError",
-      KvMutationError::InvalidMutationWithoutValue(_) => "TypeError",
-    },
-    KvError::InvalidEnqueue(e) => get_io_error_class(e),
-    KvError::EmptyKey => "TypeError",
-    KvError::ValueTooLarge(_) => "TypeError",
-    KvError::EnqueuePayloadTooLarge(_) => "TypeError",
-    KvError::InvalidCursor => "TypeError",
-    KvError::CursorOutOfBounds => "TypeError",
-    KvError::InvalidRange => "TypeError",
   }
 }
 
-fn get_net_error(error: &NetError) -> &'static str {
-  match error {
-    NetError::ListenerClosed => "BadResource",
-    NetError::ListenerBusy => "Busy",
-    NetError::SocketClosed => "BadResource",
-    NetError::SocketClosedNotConnected => "NotConnected",
-    NetError::SocketBusy => "Busy",
-    NetError::Io(e) => get_io_error_class(e),
-    NetError::AcceptTaskOngoing => "Busy",
-    NetError::RootCertStore(e)
-    | NetError::Permission(e)
-    | NetError::Resource(e) => get_error_class_name(e).unwrap_or("Error"),
-    NetError::NoResolvedAddress => "Error",
-    NetError::AddrParse(_) => "Error",
-    NetError::Map(e) => get_net_map_error(e),
-    NetError::Canceled(e) => {
-      let io_err: io::Error = e.to_owned().into();
-      get_io_error_class(&io_err)
+impl JsErrorClass for BroadcastChannelError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      BroadcastChannelError::Resource(err) => {
+        deno_core::error::get_custom_error_class(err).unwrap()
+      }
+      BroadcastChannelError::MPSCSendError(_) => "Error",
+      BroadcastChannelError::BroadcastSendError(_) => "Error",
+      BroadcastChannelError::Other(err) => {
+        get_error_class_name(err).unwrap_or("Error")
+      }
+    }
+  }
+}
+
+impl JsErrorClass for deno_kv::sqlite::SqliteBackendError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      deno_kv::sqlite::SqliteBackendError::Sqlite(e) => {
+        get_sqlite_error_class(e)
+      }
+      deno_kv::sqlite::SqliteBackendError::InvalidQueryHandle => "TypeError",
+      deno_kv::sqlite::SqliteBackendError::InvalidVersionstamp => "TypeError",
+    }
+  }
+}
+
+impl JsErrorClass for KvError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      KvError::DatabaseHandler(e) | KvError::Resource(e) | KvError::Kv(e) => {
+        get_error_class_name(e).unwrap_or("Error")
+      }
+      KvError::TooManyRanges(_) => "TypeError",
+      KvError::TooManyEntries(_) => "TypeError",
+      KvError::TooManyChecks(_) => "TypeError",
+      KvError::TooManyMutations(_) => "TypeError",
+      KvError::TooManyKeys(_) => "TypeError",
+      KvError::InvalidLimit => "TypeError",
+      KvError::InvalidBoundaryKey => "TypeError",
+      KvError::KeyTooLargeToRead(_) => "TypeError",
+      KvError::KeyTooLargeToWrite(_) => "TypeError",
+      KvError::TotalMutationTooLarge(_) => "TypeError",
+      KvError::TotalKeyTooLarge(_) => "TypeError",
+      KvError::Io(e) => get_io_error_class(e),
+      KvError::QueueMessageNotFound => "TypeError",
+      KvError::StartKeyNotInKeyspace => "TypeError",
+      KvError::EndKeyNotInKeyspace => "TypeError",
+      KvError::StartKeyGreaterThanEndKey => "TypeError",
+      KvError::InvalidCheck(e) => match e {
+        KvCheckError::InvalidVersionstamp => "TypeError",
+        KvCheckError::Io(e) => get_io_error_class(e),
+      },
+      KvError::InvalidMutation(e) => match e {
+        KvMutationError::BigInt(_) => "Error",
+        KvMutationError::Io(e) => get_io_error_class(e),
+        KvMutationError::InvalidMutationWithValue(_) => "TypeError",
+        KvMutationError::InvalidMutationWithoutValue(_) => "TypeError",
+      },
+      KvError::InvalidEnqueue(e) => get_io_error_class(e),
+      KvError::EmptyKey => "TypeError",
+      KvError::ValueTooLarge(_) => "TypeError",
+      KvError::EnqueuePayloadTooLarge(_) => "TypeError",
+      KvError::InvalidCursor => "TypeError",
+      KvError::CursorOutOfBounds => "TypeError",
+      KvError::InvalidRange => "TypeError",
+    }
+  }
+}
+
+// metatypedev/deno#chunk0-3 (first-class fetch/connection timeout with a
+// TimedOut error class) is BLOCKED / out of scope for this checkout: it
+// needs a per-request timeout in the http_util client layer plus a new
+// NetError::TimedOut/HttpError::TimedOut variant, and neither http_util
+// nor the deno_net crate that owns NetError is part of this tree. Nothing
+// below classifies a timeout distinctly from DnsTimedOut/Canceled.
+impl JsErrorClass for NetError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      NetError::ListenerClosed => "BadResource",
+      NetError::ListenerBusy => "Busy",
+      NetError::SocketClosed => "BadResource",
+      NetError::SocketClosedNotConnected => "NotConnected",
+      NetError::SocketBusy => "Busy",
+      NetError::Io(e) => get_io_error_class(e),
+      NetError::AcceptTaskOngoing => "Busy",
+      NetError::RootCertStore(e)
+      | NetError::Permission(e)
+      | NetError::Resource(e) => get_error_class_name(e).unwrap_or("Error"),
+      NetError::NoResolvedAddress => "Error",
+      NetError::AddrParse(_) => "Error",
+      NetError::Map(e) => e.error_class(),
+      NetError::Canceled(e) => {
+        let io_err: io::Error = e.to_owned().into();
+        get_io_error_class(&io_err)
+      }
+      NetError::DnsNotFound(_) => "NotFound",
+      NetError::DnsNotConnected(_) => "NotConnected",
+      NetError::DnsTimedOut(_) => "TimedOut",
+      NetError::Dns(_) => "Error",
+      NetError::UnsupportedRecordType => "NotSupported",
+      NetError::InvalidUtf8(_) => "InvalidData",
+      NetError::UnexpectedKeyType => "Error",
+      NetError::InvalidHostname(_) => "TypeError",
+      NetError::TcpStreamBusy => "Busy",
+      NetError::Rustls(e) => get_rustls_error_class(e),
+      NetError::Tls(e) => e.error_class(),
+      NetError::ListenTlsRequiresKey => "InvalidData",
+      NetError::Reunite(_) => "Error",
+    }
+  }
+}
+
+impl JsErrorClass for deno_net::io::MapError {
+  fn error_class(&self) -> &'static str {
+    match self {
+      deno_net::io::MapError::Io(e) => get_io_error_class(e),
+      deno_net::io::MapError::NoResources => "Error",
     }
-    NetError::DnsNotFound(_) => "NotFound",
-    NetError::DnsNotConnected(_) => "NotConnected",
-    NetError::DnsTimedOut(_) => "TimedOut",
-    NetError::Dns(_) => "Error",
-    NetError::UnsupportedRecordType => "NotSupported",
-    NetError::InvalidUtf8(_) => "InvalidData",
-    NetError::UnexpectedKeyType => "Error",
-    NetError::InvalidHostname(_) => "TypeError",
-    NetError::TcpStreamBusy => "Busy",
-    NetError::Rustls(_) => "Error",
-    NetError::Tls(e) => get_tls_error_class(e),
-    NetError::ListenTlsRequiresKey => "InvalidData",
-    NetError::Reunite(_) => "Error",
-  }
-}
-
-fn get_net_map_error(error: &deno_net::io::MapError) -> &'static str {
-  match error {
-    deno_net::io::MapError::Io(e) => get_io_error_class(e),
-    deno_net::io::MapError::NoResources => "Error",
   }
 }
 
@@ -443,73 +643,37 @@ pub fn get_error_class_name(e: &AnyError) -> Option<&'static str> {
   deno_core::error::get_custom_error_class(e)
     .or_else(|| deno_webgpu::error::get_error_class_name(e))
     .or_else(|| deno_websocket::get_network_error_class_name(e))
-    .or_else(|| e.downcast_ref::<WebError>().map(get_web_error_class))
-    .or_else(|| {
-      e.downcast_ref::<CompressionError>()
-        .map(get_web_compression_error_class)
-    })
-    .or_else(|| {
-      e.downcast_ref::<MessagePortError>()
-        .map(get_web_message_port_error_class)
-    })
-    .or_else(|| {
-      e.downcast_ref::<StreamResourceError>()
-        .map(get_web_stream_resource_error_class)
-    })
-    .or_else(|| e.downcast_ref::<BlobError>().map(get_web_blob_error_class))
+    .or_else(|| downcast_class::<WebError>(e))
+    .or_else(|| downcast_class::<CompressionError>(e))
+    .or_else(|| downcast_class::<MessagePortError>(e))
+    .or_else(|| downcast_class::<StreamResourceError>(e))
+    .or_else(|| downcast_class::<BlobError>(e))
     .or_else(|| e.downcast_ref::<IRError>().map(|_| "TypeError"))
-    .or_else(|| e.downcast_ref::<ReprError>().map(get_ffi_repr_error_class))
-    .or_else(|| {
-      e.downcast_ref::<DlfcnError>()
-        .map(get_ffi_dlfcn_error_class)
-    })
-    .or_else(|| {
-      e.downcast_ref::<StaticError>()
-        .map(get_ffi_static_error_class)
-    })
-    .or_else(|| {
-      e.downcast_ref::<CallbackError>()
-        .map(get_ffi_callback_error_class)
-    })
-    .or_else(|| e.downcast_ref::<CallError>().map(get_ffi_call_error_class))
-    .or_else(|| e.downcast_ref::<TlsError>().map(get_tls_error_class))
-    .or_else(|| e.downcast_ref::<CronError>().map(get_cron_error_class))
-    .or_else(|| e.downcast_ref::<CanvasError>().map(get_canvas_error))
-    .or_else(|| e.downcast_ref::<CacheError>().map(get_cache_error))
-    .or_else(|| e.downcast_ref::<KvError>().map(get_kv_error))
-    .or_else(|| e.downcast_ref::<NetError>().map(get_net_error))
-    .or_else(|| {
-      e.downcast_ref::<deno_net::io::MapError>()
-        .map(get_net_map_error)
-    })
-    .or_else(|| {
-      e.downcast_ref::<BroadcastChannelError>()
-        .map(get_broadcast_channel_error)
-    })
-    .or_else(|| {
-      e.downcast_ref::<WebStorageError>()
-        .map(get_webstorage_class_name)
-    })
+    .or_else(|| downcast_class::<ReprError>(e))
+    .or_else(|| downcast_class::<DlfcnError>(e))
+    .or_else(|| downcast_class::<StaticError>(e))
+    .or_else(|| downcast_class::<CallbackError>(e))
+    .or_else(|| downcast_class::<CallError>(e))
+    .or_else(|| downcast_class::<TlsError>(e))
+    .or_else(|| downcast_class::<CronError>(e))
+    .or_else(|| downcast_class::<CanvasError>(e))
+    .or_else(|| downcast_class::<CacheError>(e))
+    .or_else(|| downcast_class::<KvError>(e))
+    .or_else(|| downcast_class::<NetError>(e))
+    .or_else(|| downcast_class::<deno_net::io::MapError>(e))
+    .or_else(|| downcast_class::<BroadcastChannelError>(e))
+    .or_else(|| downcast_class::<WebStorageError>(e))
     .or_else(|| {
       e.downcast_ref::<deno_url::UrlPatternError>()
         .map(|_| "TypeError")
     })
-    .or_else(|| {
-      e.downcast_ref::<dlopen2::Error>()
-        .map(get_dlopen_error_class)
-    })
-    .or_else(|| e.downcast_ref::<hyper::Error>().map(get_hyper_error_class))
-    .or_else(|| {
-      e.downcast_ref::<hyper_util::client::legacy::Error>()
-        .map(get_hyper_util_error_class)
-    })
-    .or_else(|| {
-      e.downcast_ref::<hyper_v014::Error>()
-        .map(get_hyper_v014_error_class)
-    })
+    .or_else(|| downcast_class::<dlopen2::Error>(e))
+    .or_else(|| downcast_class::<hyper::Error>(e))
+    .or_else(|| downcast_class::<hyper_util::client::legacy::Error>(e))
+    .or_else(|| downcast_class::<hyper_v014::Error>(e))
     .or_else(|| {
       e.downcast_ref::<Arc<hyper_v014::Error>>()
-        .map(|e| get_hyper_v014_error_class(e))
+        .map(|e| e.error_class())
     })
     .or_else(|| {
       e.downcast_ref::<deno_core::Canceled>().map(|e| {
@@ -517,38 +681,26 @@ pub fn get_error_class_name(e: &AnyError) -> Option<&'static str> {
         get_io_error_class(&io_err)
       })
     })
-    .or_else(|| {
-      e.downcast_ref::<env::VarError>()
-        .map(get_env_var_error_class)
-    })
-    .or_else(|| e.downcast_ref::<io::Error>().map(get_io_error_class))
-    .or_else(|| {
-      e.downcast_ref::<ModuleResolutionError>()
-        .map(get_module_resolution_error_class)
-    })
-    .or_else(|| {
-      e.downcast_ref::<notify::Error>()
-        .map(get_notify_error_class)
-    })
-    .or_else(|| e.downcast_ref::<regex::Error>().map(get_regex_error_class))
-    .or_else(|| {
-      e.downcast_ref::<serde_json::error::Error>()
-        .map(get_serde_json_error_class)
-    })
-    .or_else(|| {
-      e.downcast_ref::<url::ParseError>()
-        .map(get_url_parse_error_class)
-    })
-    .or_else(|| {
-      e.downcast_ref::<deno_kv::sqlite::SqliteBackendError>()
-        .map(|_| "TypeError")
-    })
+    .or_else(|| downcast_class::<env::VarError>(e))
+    .or_else(|| downcast_class::<io::Error>(e))
+    .or_else(|| downcast_class::<ModuleResolutionError>(e))
+    .or_else(|| downcast_class::<notify::Error>(e))
+    .or_else(|| downcast_class::<regex::Error>(e))
+    .or_else(|| downcast_class::<serde_json::error::Error>(e))
+    .or_else(|| downcast_class::<url::ParseError>(e))
+    .or_else(|| downcast_class::<deno_kv::sqlite::SqliteBackendError>(e))
     .or_else(|| {
       #[cfg(unix)]
-      let maybe_get_nix_error_class =
-        || e.downcast_ref::<nix::Error>().map(get_nix_error_class);
+      let maybe_get_nix_error_class = || downcast_class::<nix::Error>(e);
       #[cfg(not(unix))]
       let maybe_get_nix_error_class = || Option::<&'static str>::None;
       (maybe_get_nix_error_class)()
     })
+    .or_else(|| {
+      EMBEDDER_CLASSIFIERS
+        .read()
+        .unwrap()
+        .iter()
+        .find_map(|f| f(e))
+    })
 }